@@ -1,7 +1,8 @@
 use anyhow::{anyhow, Result};
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
-use serde::Deserialize;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::fs::File;
 use std::io::{Read, Write};
@@ -22,12 +23,82 @@ fn directory_bin() -> PathBuf {
     bin
 }
 
+/// The directory suivm installs Sui binaries and state into
+pub fn install_dir() -> PathBuf {
+    directory_suivm()
+}
+
+/// Returns `true` if a `sui` executable is discoverable on `PATH`, i.e. the
+/// shim installed by suivm has been linked in
+pub fn shim_on_path() -> bool {
+    let Some(path_var) = std::env::var_os("PATH") else {
+        return false;
+    };
+
+    #[cfg(windows)]
+    let shim_name = "sui.exe";
+    #[cfg(not(windows))]
+    let shim_name = "sui";
+
+    std::env::split_paths(&path_var).any(|dir| dir.join(shim_name).is_file())
+}
+
+/// GitHub API rate-limit status for the current (optionally `GITHUB_TOKEN`
+/// authenticated) requests
+#[derive(Debug)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+}
+
+/// Checks connectivity to the GitHub API and reports the current rate-limit
+/// window
+pub fn check_github_rate_limit() -> Result<RateLimit> {
+    #[derive(Deserialize)]
+    struct RateLimitResponse {
+        rate: RateLimitInner,
+    }
+    #[derive(Deserialize)]
+    struct RateLimitInner {
+        limit: u32,
+        remaining: u32,
+    }
+
+    let mut request = ureq::get("https://api.github.com/rate_limit");
+    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        request = request.set("Authorization", &format!("Bearer {token}"));
+    }
+
+    let body: RateLimitResponse = serde_json::from_reader(request.call()?.into_reader())?;
+    Ok(RateLimit {
+        limit: body.rate.limit,
+        remaining: body.rate.remaining,
+    })
+}
+
 fn path_version() -> PathBuf {
     let mut path = directory_suivm();
     path.push(".version");
     path
 }
 
+const PROJECT_VERSION_FILE: &str = ".sui-version";
+
+/// Walks up from the current directory looking for a `.sui-version` file
+fn find_project_version_file() -> Option<PathBuf> {
+    let mut dir = std::env::current_dir().ok()?;
+    loop {
+        let candidate = dir.join(PROJECT_VERSION_FILE);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
 pub fn path_bin(version: &str) -> PathBuf {
     let mut path = directory_bin();
     path.push(version);
@@ -44,15 +115,56 @@ pub fn current_version() -> Option<String> {
     })
 }
 
+/// Read the project-local pinned version, if any, by walking up from the
+/// current directory looking for a `.sui-version` file
+pub fn project_version() -> Option<String> {
+    File::open(find_project_version_file()?).ok().and_then(|mut file| {
+        let mut v = String::new();
+        file.read_to_string(&mut v).unwrap();
+
+        (!v.is_empty()).then_some(v)
+    })
+}
+
+/// Resolves the version that should be used in the current directory: a
+/// project-local `.sui-version` pin takes precedence over the global version
+pub fn resolve_effective_version() -> Option<String> {
+    project_version().or_else(current_version)
+}
+
+/// Pin a Sui version for the current project by writing a `.sui-version` file
+pub fn pin_version(alias: &String) -> Result<String> {
+    let version = fetch_version(alias)?;
+
+    let mut file = File::create(PROJECT_VERSION_FILE)?;
+    file.write_all(version.as_bytes())?;
+
+    Ok(version)
+}
+
+/// Finds the installed binary name for `version`, accounting for commit
+/// builds stored under a `<semver>+<short-sha>` label rather than the raw ref
+pub fn resolve_installed_label(version: &str) -> Option<String> {
+    let installed_versions = fetch_installed_versions();
+    if installed_versions.iter().any(|installed| installed == version) {
+        return Some(version.to_string());
+    }
+
+    let suffix = format!("+{}", short_ref(version));
+    installed_versions
+        .into_iter()
+        .find(|installed| installed.ends_with(&suffix))
+}
+
 /// Install and use Sui version
 pub fn use_version(alias: &String, compile: bool) -> Result<()> {
     let version = fetch_version(alias)?;
 
     // Make sure the requested version is installed
-    let installed_versions = fetch_installed_versions();
-    if !installed_versions.contains(&version) {
-        install_version(alias, compile)?;
-    }
+    let version = match resolve_installed_label(&version) {
+        Some(label) => label,
+        None => install_version(alias, compile)?,
+    };
 
     let mut current_version_file = File::create(path_version().as_path())?;
     current_version_file.write_all(version.as_bytes())?;
@@ -61,24 +173,48 @@ pub fn use_version(alias: &String, compile: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn install_version(alias: &String, _compile: bool) -> Result<()> {
+/// Installs a Sui version, returning the installed binary's name (a commit
+/// build is named `<semver>+<short-sha>` rather than the raw ref, unless its
+/// manifest version can't be fetched)
+pub fn install_version(alias: &String, _compile: bool) -> Result<String> {
     let version = fetch_version(alias)?;
 
     println!("Installing Sui `{alias} ({version})`");
 
     if !_compile {
         let available_versions = fetch_versions()?;
-        if available_versions.contains(alias) {
+        if available_versions.contains(&version) {
             download_version(&version)?;
             println!("Downloaded Sui `{alias} ({version})`");
-            return Ok(());
+            return Ok(version);
         }
     }
 
-    compile_version(&version)?;
-    println!("Compiled Sui `{alias} ({version})`");
+    let label = compile_version(&version)?;
+    println!("Compiled Sui `{alias} ({label})`");
 
-    Ok(())
+    Ok(label)
+}
+
+/// Returns the published Sui release asset suffix for the current OS/arch,
+/// or `None` when there is no prebuilt download target for this platform
+pub fn os_postfix() -> Option<&'static str> {
+    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+    return Some("macos-arm64");
+    #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+    return Some("macos-x86_64");
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    return Some("ubuntu-x86_64");
+    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
+    return Some("windows-x86_64");
+
+    #[cfg(not(any(
+        all(target_os = "macos", target_arch = "aarch64"),
+        all(target_os = "macos", target_arch = "x86_64"),
+        all(target_os = "linux", target_arch = "x86_64"),
+        all(target_os = "windows", target_arch = "x86_64"),
+    )))]
+    None
 }
 
 fn download_version(version: &String) -> Result<()> {
@@ -92,14 +228,9 @@ fn download_version(version: &String) -> Result<()> {
 
     let mut tar_gz_buffer: Vec<u8> = vec![];
 
-    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-    let os_postfix = "macos-arm64";
-    #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
-    let os_postfix = "macos-x86_64";
-    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
-    let os_postfix = "ubuntu-x86_64";
-    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
-    let os_postfix = "windows-x86_64";
+    let os_postfix = os_postfix().ok_or_else(|| {
+        anyhow!("No prebuilt Sui binary is published for this platform, use `--compile`")
+    })?;
 
     let res = ureq::get(&format!(
         "https://github.com/MystenLabs/sui/releases/download/{version}/sui-{version}-{os_postfix}.tgz",
@@ -166,7 +297,43 @@ fn download_version(version: &String) -> Result<()> {
     Ok(())
 }
 
-fn compile_version(version: &String) -> Result<()> {
+/// Returns the short form of a 40-character commit sha, or `version`
+/// unchanged if it isn't one (e.g. a branch name or release tag)
+fn short_ref(version: &str) -> &str {
+    let is_sha = version.len() == 40 && version.chars().all(|c| c.is_ascii_hexdigit());
+    if is_sha {
+        &version[..7]
+    } else {
+        version
+    }
+}
+
+/// Fetches `package.version` from `crates/sui/Cargo.toml` at `revision` on
+/// GitHub, so commit/branch installs can be labelled with a human-readable
+/// version instead of an opaque ref
+fn fetch_manifest_version(revision: &str) -> Result<String> {
+    #[derive(Deserialize)]
+    struct Manifest {
+        package: Package,
+    }
+    #[derive(Deserialize)]
+    struct Package {
+        version: String,
+    }
+
+    let body = ureq::get(&format!(
+        "https://raw.githubusercontent.com/MystenLabs/sui/{revision}/crates/sui/Cargo.toml"
+    ))
+    .call()?
+    .into_string()?;
+
+    let manifest: Manifest = toml::from_str(&body)?;
+    Ok(manifest.package.version)
+}
+
+/// Compiles Sui at `version` (a tag, branch, or commit), returning the
+/// installed binary's name
+fn compile_version(version: &String) -> Result<String> {
     let directory = directory_suivm();
     let exit = std::process::Command::new("cargo")
         .args([
@@ -190,14 +357,33 @@ fn compile_version(version: &String) -> Result<()> {
         return Err(anyhow!("Failed to compile Sui `{version}`"));
     }
 
-    fs::rename(path_bin("sui"), path_bin(version))?;
+    // Non-tag installs (branches, commits) are recorded under a
+    // human-readable `<version>+<short-sha>` name rather than the raw ref,
+    // while still resolving back to it via `resolve_installed_label`. If the
+    // manifest version can't be fetched, the raw ref is kept as the name. A
+    // forced `--compile` of an already-known release tag keeps the plain tag.
+    let is_known_release = fetch_versions()
+        .map(|available| available.contains(version))
+        .unwrap_or(false);
+
+    let label = if is_known_release {
+        version.clone()
+    } else {
+        match fetch_manifest_version(version) {
+            Ok(semver) => format!("{semver}+{}", short_ref(version)),
+            Err(_) => version.clone(),
+        }
+    };
 
-    Ok(())
+    fs::rename(path_bin("sui"), path_bin(&label))?;
+
+    Ok(label)
 }
 
 /// Uninstall Sui version
 pub fn uninstall_version(alias: &String) -> Result<()> {
     let version = fetch_version(alias)?;
+    let version = resolve_installed_label(&version).unwrap_or(version);
 
     let path = &path_bin(&version);
     if path.as_path().exists() {
@@ -216,10 +402,83 @@ pub fn uninstall_version(alias: &String) -> Result<()> {
     Ok(())
 }
 
+/// Strips a release's channel prefix (e.g. `testnet-v1.20.3` -> `1.20.3`) so
+/// the remainder can be parsed as a [`Version`]
+fn strip_channel_prefix(tag: &str) -> &str {
+    let rest = match version_channel(tag) {
+        Some(channel) => &tag[channel.len() + 1..],
+        None => tag,
+    };
+    rest.trim_start_matches('v')
+}
+
+/// Parses the semver portion of a release tag, skipping tags that don't carry
+/// a valid version (e.g. malformed tags) instead of erroring
+fn parse_tag_version(tag: &str) -> Option<Version> {
+    Version::parse(strip_channel_prefix(tag)).ok()
+}
+
+/// Finds the highest published release tag satisfying `req`
+fn resolve_version_req(req: &VersionReq, available_versions: &[String]) -> Option<String> {
+    available_versions
+        .iter()
+        .filter_map(|tag| parse_tag_version(tag).map(|version| (tag, version)))
+        .filter(|(_, version)| req.matches(version))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(tag, _)| tag.clone())
+}
+
+/// Sui's parallel release lines, identified by their tag prefix
+pub const CHANNELS: [&str; 3] = ["mainnet", "testnet", "devnet"];
+
+/// Returns the channel a release tag belongs to, if any (e.g. `testnet-v1.20.3`
+/// belongs to `testnet`)
+pub fn version_channel(tag: &str) -> Option<&'static str> {
+    CHANNELS
+        .iter()
+        .find(|channel| tag.starts_with(&format!("{channel}-")))
+        .copied()
+}
+
+/// Resolves the newest published release tag on a given channel
+pub fn fetch_channel_version(channel: &str) -> Result<String> {
+    let available_versions = fetch_versions()?;
+    let prefix = format!("{channel}-");
+
+    available_versions
+        .iter()
+        .filter(|tag| tag.starts_with(&prefix))
+        .filter_map(|tag| parse_tag_version(tag).map(|version| (tag, version)))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(tag, _)| tag.clone())
+        .ok_or_else(|| anyhow!("no release found on channel `{channel}`"))
+}
+
+/// If `installed` is a `<semver>+<short-sha>` commit build label, returns the
+/// channel of a published release sharing that semver, if any
+pub fn commit_build_channel(installed: &str, available_versions: &[String]) -> Option<String> {
+    let (version, _) = installed.split_once('+')?;
+    let target = Version::parse(version).ok()?;
+
+    available_versions.iter().find_map(|tag| {
+        let matches = parse_tag_version(tag).is_some_and(|tag_version| tag_version == target);
+        matches.then(|| version_channel(tag)).flatten().map(str::to_string)
+    })
+}
+
 /// Resolves aliases to their commit hash
-fn fetch_version(alias: &String) -> Result<String> {
+pub fn fetch_version(alias: &String) -> Result<String> {
     match fetch_versions() {
         Ok(available_versions) => {
+            if CHANNELS.contains(&alias.as_str()) {
+                return fetch_channel_version(alias);
+            }
+
+            if let Ok(req) = VersionReq::parse(alias) {
+                return resolve_version_req(&req, &available_versions)
+                    .ok_or_else(|| anyhow!("no release satisfies `{alias}`"));
+            }
+
             if available_versions.contains(alias) {
                 return Ok(alias.clone());
             }
@@ -237,21 +496,115 @@ fn fetch_version(alias: &String) -> Result<String> {
     Err(anyhow!("`{alias}` is neither a valid version, branch, or commit, check available versions using `suivm list`"))
 }
 
-/// Retrieve a list of installable versions of sui using the GitHub API and tags
-/// on the Sui repository.
-pub fn fetch_versions() -> Result<Vec<String>> {
+/// How long a cached version list is considered fresh before a live fetch is
+/// made again.
+const VERSIONS_CACHE_TTL_SECS: u64 = 6 * 60 * 60;
+
+fn path_versions_cache() -> PathBuf {
+    let mut path = directory_suivm();
+    path.push("versions.json");
+    path
+}
+
+#[derive(Serialize, Deserialize)]
+struct VersionsCache {
+    fetched_at: u64,
+    versions: Vec<String>,
+}
+
+fn now_unix() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn read_versions_cache() -> Option<VersionsCache> {
+    let file = File::open(path_versions_cache()).ok()?;
+    serde_json::from_reader(file).ok()
+}
+
+fn write_versions_cache(versions: &[String]) -> Result<()> {
+    let cache = VersionsCache {
+        fetched_at: now_unix(),
+        versions: versions.to_vec(),
+    };
+    let file = File::create(path_versions_cache())?;
+    serde_json::to_writer(file, &cache)?;
+    Ok(())
+}
+
+/// Deletes the on-disk cache of available Sui versions
+pub fn clear_cache() -> Result<()> {
+    let path = path_versions_cache();
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Extracts the `rel="next"` URL from a GitHub `Link` response header, if any
+fn parse_next_link(header: Option<&str>) -> Option<String> {
+    header?.split(',').find_map(|part| {
+        let mut segments = part.split(';');
+        let url = segments
+            .next()?
+            .trim()
+            .trim_start_matches('<')
+            .trim_end_matches('>');
+        segments
+            .any(|segment| segment.trim() == "rel=\"next\"")
+            .then(|| url.to_string())
+    })
+}
+
+/// Fetches the full, paginated release list directly from the GitHub API,
+/// authenticating with `GITHUB_TOKEN` when set to raise the rate limit.
+fn fetch_versions_live() -> Result<Vec<String>> {
     #[derive(Deserialize, Debug)]
     struct Release {
         tag_name: String,
     }
 
-    let file =
-        ureq::get("https://api.github.com/repos/MystenLabs/sui/releases")
-            .call()?
-            .into_reader();
+    let mut tags = vec![];
+    let mut url =
+        "https://api.github.com/repos/MystenLabs/sui/releases?per_page=100".to_string();
+
+    loop {
+        let mut request = ureq::get(&url);
+        if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+            request = request.set("Authorization", &format!("Bearer {token}"));
+        }
+
+        let res = request.call()?;
+        let next_url = parse_next_link(res.header("Link"));
+
+        let releases: Vec<Release> = serde_json::from_reader(res.into_reader())?;
+        tags.extend(releases.into_iter().map(|r| r.tag_name));
+
+        match next_url {
+            Some(next) => url = next,
+            None => break,
+        }
+    }
+
+    tags.reverse();
+    Ok(tags)
+}
+
+/// Retrieve a list of installable versions of sui using the GitHub API and tags
+/// on the Sui repository. Cached on disk for [`VERSIONS_CACHE_TTL_SECS`] to
+/// avoid tripping GitHub's unauthenticated rate limit on every invocation.
+pub fn fetch_versions() -> Result<Vec<String>> {
+    if let Some(cache) = read_versions_cache() {
+        if now_unix().saturating_sub(cache.fetched_at) < VERSIONS_CACHE_TTL_SECS {
+            return Ok(cache.versions);
+        }
+    }
 
-    let versions: Vec<Release> = serde_json::from_reader(file)?;
-    Ok(versions.into_iter().map(|r| r.tag_name).rev().collect())
+    let versions = fetch_versions_live()?;
+    write_versions_cache(&versions)?;
+    Ok(versions)
 }
 
 pub fn fetch_latest_version() -> Result<String> {
@@ -294,3 +647,98 @@ pub fn fetch_installed_versions() -> Vec<String> {
         .filter(|name| !name.starts_with('.'))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_channel_prefix_keeps_prerelease_hyphen() {
+        assert_eq!(strip_channel_prefix("testnet-v1.20.3-rc.1"), "1.20.3-rc.1");
+        assert_eq!(strip_channel_prefix("mainnet-v1.21.0"), "1.21.0");
+        assert_eq!(strip_channel_prefix("v1.20.3"), "1.20.3");
+    }
+
+    #[test]
+    fn parse_tag_version_handles_prerelease_and_malformed_tags() {
+        assert_eq!(
+            parse_tag_version("testnet-v1.20.3-rc.1"),
+            Version::parse("1.20.3-rc.1").ok()
+        );
+        assert_eq!(parse_tag_version("not-a-version"), None);
+    }
+
+    #[test]
+    fn resolve_version_req_picks_highest_match() {
+        let available = vec![
+            "testnet-v1.19.0".to_string(),
+            "testnet-v1.20.3".to_string(),
+            "testnet-v1.21.0".to_string(),
+        ];
+        let req = VersionReq::parse("~1.20").unwrap();
+        assert_eq!(
+            resolve_version_req(&req, &available),
+            Some("testnet-v1.20.3".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_version_req_returns_none_when_nothing_matches() {
+        let available = vec!["testnet-v1.19.0".to_string()];
+        let req = VersionReq::parse(">=2.0").unwrap();
+        assert_eq!(resolve_version_req(&req, &available), None);
+    }
+
+    #[test]
+    fn parse_next_link_extracts_next_url() {
+        let header = r#"<https://api.github.com/x?page=2>; rel="next", <https://api.github.com/x?page=5>; rel="last""#;
+        assert_eq!(
+            parse_next_link(Some(header)),
+            Some("https://api.github.com/x?page=2".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_next_link_returns_none_on_last_page() {
+        let header = r#"<https://api.github.com/x?page=1>; rel="prev""#;
+        assert_eq!(parse_next_link(Some(header)), None);
+        assert_eq!(parse_next_link(None), None);
+    }
+
+    #[test]
+    fn short_ref_truncates_full_shas_only() {
+        let sha = "a".repeat(40);
+        assert_eq!(short_ref(&sha), &sha[..7]);
+        assert_eq!(short_ref("testnet-v1.20.3"), "testnet-v1.20.3");
+        assert_eq!(short_ref("main"), "main");
+    }
+
+    #[test]
+    fn resolve_installed_label_matches_exact_and_commit_build() {
+        let home = std::env::temp_dir().join(format!("suivm-test-{}", std::process::id()));
+        std::fs::create_dir_all(home.join(".suivm/bin")).unwrap();
+        std::fs::write(home.join(".suivm/bin/testnet-v1.20.3"), b"").unwrap();
+        std::fs::write(home.join(".suivm/bin/1.21.0+abc1234"), b"").unwrap();
+
+        let previous_home = std::env::var_os("HOME");
+        std::env::set_var("HOME", &home);
+
+        let sha = format!("abc1234{}", "0".repeat(33));
+
+        assert_eq!(
+            resolve_installed_label("testnet-v1.20.3"),
+            Some("testnet-v1.20.3".to_string())
+        );
+        assert_eq!(
+            resolve_installed_label(&sha),
+            Some("1.21.0+abc1234".to_string())
+        );
+        assert_eq!(resolve_installed_label("unknown"), None);
+
+        match previous_home {
+            Some(value) => std::env::set_var("HOME", value),
+            None => std::env::remove_var("HOME"),
+        }
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+}
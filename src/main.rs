@@ -1,5 +1,6 @@
 use anyhow::Result;
 use clap::Parser;
+use std::fs;
 use suivm::fetch_versions;
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -9,11 +10,23 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 #[command(bin_name = "suivm")]
 enum Suivm {
     #[clap(about = "List latest Sui version")]
-    Latest,
+    Latest {
+        /// Sui release channel (mainnet, testnet, devnet)
+        channel: Option<String>,
+        /// Bypass the cached version list and fetch live from GitHub
+        #[arg(long)]
+        refresh: bool,
+    },
     #[clap(about = "List all available Sui versions")]
-    List,
+    List {
+        /// Bypass the cached version list and fetch live from GitHub
+        #[arg(long)]
+        refresh: bool,
+    },
     #[clap(about = "List all installed Sui versions")]
     Installed,
+    #[clap(about = "Clears the cached list of available Sui versions")]
+    ClearCache,
     #[clap(about = "Display current Sui version")]
     Status,
     #[clap(about = "Uninstalls Sui version")]
@@ -34,23 +47,37 @@ enum Suivm {
         /// Sui release tag, branch, or revision
         version: String,
     },
+    #[clap(about = "Pin a Sui version for the current project via `.sui-version`")]
+    Pin {
+        /// Sui release tag, branch, or revision
+        version: String,
+    },
+    #[clap(about = "Print a diagnostic report of the suivm environment")]
+    Doctor,
 }
 
 fn print_version(
     installed_versions: &Vec<String>,
+    available_versions: &[String],
     latest: &Option<String>,
     current: &Option<String>,
     version: &String,
 ) {
     let mut flags = vec![];
+    if let Some(channel) = suivm::version_channel(version) {
+        flags.push(channel.to_string());
+    }
     if matches!(latest, Some(latest) if latest == version) {
-        flags.push("latest");
+        flags.push("latest".to_string());
     }
     if installed_versions.contains(version) {
-        flags.push("installed");
+        flags.push("installed".to_string());
     }
     if matches!(current, Some(current) if current == version) {
-        flags.push("current");
+        flags.push("current".to_string());
+    }
+    if let Some(channel) = suivm::commit_build_channel(version, available_versions) {
+        flags.push(format!("commit build tracking {channel}"));
     }
 
     if flags.is_empty() {
@@ -60,7 +87,11 @@ fn print_version(
     }
 }
 
-fn print_versions() {
+fn print_versions(refresh: bool) {
+    if refresh {
+        let _ = suivm::clear_cache();
+    }
+
     let available_versions = match fetch_versions() {
         Ok(versions) => versions,
         Err(err) => return println!("Could not fetch versions: {err}"),
@@ -70,13 +101,21 @@ fn print_versions() {
     let installed_versions = &suivm::fetch_installed_versions();
     let latest = available_versions.last().cloned();
 
-    for version in available_versions {
-        print_version(&installed_versions, &latest, &current, &version);
+    for version in &available_versions {
+        print_version(installed_versions, &available_versions, &latest, &current, version);
     }
 }
 
-fn print_latest_version() {
-    let latest = match suivm::fetch_latest_version() {
+fn print_latest_version(channel: Option<String>, refresh: bool) {
+    if refresh {
+        let _ = suivm::clear_cache();
+    }
+
+    let latest = match &channel {
+        Some(channel) => suivm::fetch_channel_version(channel),
+        None => suivm::fetch_latest_version(),
+    };
+    let latest = match latest {
         Ok(latest) => latest,
         Err(err) => return println!("Could not fetch latest version: {err}"),
     };
@@ -84,29 +123,76 @@ fn print_latest_version() {
     let current = suivm::current_version();
     let installed_versions = &suivm::fetch_installed_versions();
 
-    print_version(&installed_versions, &None, &current, &latest);
+    print_version(installed_versions, &[], &None, &current, &latest);
 }
 
 fn print_installed() {
     let latest = suivm::fetch_latest_version().ok();
     let current = suivm::current_version();
+    let available_versions = fetch_versions().unwrap_or_default();
 
     for version in suivm::fetch_installed_versions() {
-        print_version(&Vec::new(), &latest, &current, &version);
+        print_version(&Vec::new(), &available_versions, &latest, &current, &version);
     }
 }
 
 fn print_current() {
     let latest = suivm::fetch_latest_version().ok();
+    let available_versions = fetch_versions().unwrap_or_default();
     match suivm::current_version() {
-        Some(current) => print_version(&Vec::new(), &latest, &None, &current),
+        Some(current) => print_version(&Vec::new(), &available_versions, &latest, &None, &current),
         None => println!("Sui is not installed. Run `suivm use latest`"),
     }
 }
 
+fn print_doctor() {
+    println!("suivm {VERSION}");
+
+    match suivm::os_postfix() {
+        Some(postfix) => println!("Platform: {postfix}"),
+        None => println!("Platform: no prebuilt Sui binary available, `--compile` is required"),
+    }
+
+    println!("Install directory: {}", suivm::install_dir().display());
+
+    match suivm::current_version() {
+        Some(version) => println!("Global version: {version}"),
+        None => println!("Global version: none (run `suivm use latest`)"),
+    }
+    match suivm::project_version() {
+        Some(version) => println!("Project version (`.sui-version`): {version}"),
+        None => println!("Project version (`.sui-version`): none"),
+    }
+
+    println!("Shim on PATH: {}", suivm::shim_on_path());
+
+    println!("Installed versions:");
+    let installed_versions = suivm::fetch_installed_versions();
+    if installed_versions.is_empty() {
+        println!("  (none)");
+    } else {
+        for version in installed_versions {
+            let size = fs::metadata(suivm::path_bin(&version))
+                .map(|meta| format!("{} bytes", meta.len()))
+                .unwrap_or_else(|_| "unknown size".to_string());
+            println!("  {version} ({size})");
+        }
+    }
+
+    match suivm::check_github_rate_limit() {
+        Ok(rate) => println!(
+            "GitHub API: reachable ({}/{} requests remaining)",
+            rate.remaining, rate.limit
+        ),
+        Err(err) => println!("GitHub API: unreachable ({err})"),
+    }
+}
+
 fn handle_alias(alias: &str) -> Result<String> {
     if alias == "latest" {
         suivm::fetch_latest_version()
+    } else if suivm::CHANNELS.contains(&alias) {
+        suivm::fetch_channel_version(alias)
     } else {
         Ok(alias.to_string())
     }
@@ -114,18 +200,31 @@ fn handle_alias(alias: &str) -> Result<String> {
 
 fn main() -> Result<()> {
     match Suivm::parse() {
-        Suivm::Latest => Ok(print_latest_version()),
-        Suivm::List => Ok(print_versions()),
+        Suivm::Latest { channel, refresh } => Ok(print_latest_version(channel, refresh)),
+        Suivm::List { refresh } => Ok(print_versions(refresh)),
         Suivm::Installed => Ok(print_installed()),
         Suivm::Status => Ok(print_current()),
+        Suivm::ClearCache => {
+            suivm::clear_cache()?;
+            println!("Cleared cached Sui version list");
+            Ok(())
+        }
         Suivm::Uninstall { version } => suivm::uninstall_version(&version),
         Suivm::Install { compile, version } => {
             let version = handle_alias(&version)?;
-            suivm::install_version(&version, compile)
+            suivm::install_version(&version, compile)?;
+            Ok(())
         }
         Suivm::Use { compile, version } => {
             let version = handle_alias(&version)?;
             suivm::use_version(&version, compile)
         }
+        Suivm::Pin { version } => {
+            let version = handle_alias(&version)?;
+            let version = suivm::pin_version(&version)?;
+            println!("Pinned Sui `{version}` for this project");
+            Ok(())
+        }
+        Suivm::Doctor => Ok(print_doctor()),
     }
 }
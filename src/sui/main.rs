@@ -3,9 +3,18 @@ use std::{env, fs, process};
 fn main() -> anyhow::Result<()> {
     let args = env::args().skip(1).collect::<Vec<String>>();
 
-    let version = suivm::current_version().ok_or_else(|| {
+    if let Some(pinned) = suivm::project_version() {
+        if suivm::resolve_installed_label(&pinned).is_none() {
+            return Err(anyhow::Error::msg(format!(
+                "Sui `{pinned}` is pinned in `.sui-version` but not installed. Run `suivm install {pinned}`"
+            )));
+        }
+    }
+
+    let version = suivm::resolve_effective_version().ok_or_else(|| {
         anyhow::Error::msg("Sui is not installed. Run `suivm use latest`")
     })?;
+    let version = suivm::resolve_installed_label(&version).unwrap_or(version);
 
     let binary_path = suivm::path_bin(&version);
     fs::metadata(&binary_path).map_err(|_| {